@@ -1,11 +1,28 @@
 use std::{sync::Arc, time::Duration};
 
-use nimbu_core::{Task, task_queue::queue::TaskQueue};
+use async_trait::async_trait;
+use nimbu_core::{Context, RunnableTask, Task, TaskError, Worker, task_queue::queue::TaskQueue};
+use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 use tracing::info;
 
 use tracing_subscriber::{EnvFilter, fmt};
 
+/// A stand-in payload used only by this demo binary.
+#[derive(Debug, Serialize, Deserialize)]
+struct DemoTask {
+    label: String,
+}
+
+#[async_trait]
+#[typetag::serde]
+impl RunnableTask for DemoTask {
+    async fn run(&self, ctx: &Context) -> Result<(), TaskError> {
+        info!(task_id = ?ctx.task_id, label = %self.label, "running demo task");
+        Ok(())
+    }
+}
+
 pub fn init_tracing() {
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
@@ -25,51 +42,28 @@ async fn main() {
     let queue = Arc::new(TaskQueue::new(10));
 
     // Immediate task
-    let task1 = Task::new(vec![1, 2, 3, 4, 5]).build();
-    queue.enqueue(task1.clone()).await.unwrap();
+    let task1 = Task::new(Box::new(DemoTask { label: "one".into() })).build();
+    queue.enqueue(task1).await.unwrap();
 
     // Delayed tasks
-    let task2 = Task::new(vec![6, 7, 8, 9, 10]).build();
-    queue.enqueue_delayed(task2.clone(), Duration::from_secs(3));
+    let task2 = Task::new(Box::new(DemoTask { label: "two".into() })).build();
+    queue.enqueue_delayed(task2, Duration::from_secs(3));
 
-    let task3 = Task::new(vec![11, 12, 13, 14, 15]).build();
-    queue.enqueue_delayed(task3.clone(), Duration::from_secs(3));
+    let task3 = Task::new(Box::new(DemoTask { label: "three".into() })).build();
+    queue.enqueue_delayed(task3, Duration::from_secs(3));
 
     info!("tasks enqueued");
 
-    // Watch channel to signal worker shutdown
-    let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
-
-    let worker_queue = queue.clone();
-    let worker_handle = tokio::spawn(async move {
-        loop {
-            tokio::select! {
-                res = shutdown_rx.changed() => {
-                    if res.is_ok() && *shutdown_rx.borrow() {
-                        info!("worker shutting down");
-                        break;
-                    }
-                }
-
-                task_opt = worker_queue.dequeue() => {
-                    if let Some(task) = task_opt {
-                        info!(task_id = ?task.id, "worker received task");
-                    } else {
-                        // optional: small delay to avoid busy-loop when queue empty
-                        tokio::time::sleep(Duration::from_millis(50)).await;
-                    }
-                }
-            }
-        }
-    });
+    let worker = Worker::new(queue.clone(), queue.scheduler_tx.clone(), Arc::new(()));
+    let worker_handle = tokio::spawn(async move { worker.run().await });
 
     // Let system run for demo
     sleep(Duration::from_secs(10)).await;
 
     info!("shutting down scheduler");
 
-    // signal worker to stop
-    let _ = shutdown_tx.send(true);
+    // the worker loops until the queue's ready channel closes, so just abort it
+    worker_handle.abort();
     let _ = worker_handle.await;
 
     let queue = Arc::try_unwrap(queue).expect("error stopping worker queue");
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -8,6 +10,18 @@ impl JobId {
     pub fn new() -> Self {
         Self(Uuid::new_v4().to_string())
     }
+
+    /// Reconstructs a `JobId` from its raw string form, e.g. a persisted
+    /// backend row.
+    pub fn from_raw(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl Display for JobId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Hash, Eq)]
@@ -17,4 +31,16 @@ impl TaskId {
     pub fn new() -> Self {
         Self(Uuid::new_v4().to_string())
     }
+
+    /// Reconstructs a `TaskId` from its raw string form, e.g. a persisted
+    /// backend row.
+    pub fn from_raw(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl Display for TaskId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
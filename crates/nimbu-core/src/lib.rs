@@ -0,0 +1,14 @@
+pub mod ids;
+pub mod task;
+pub mod task_queue;
+
+pub use ids::{JobId, TaskId};
+pub use task::{
+    BackoffStrategy, RetryPolicy, Task, TaskBuilder, TaskResult, TaskStatus, TaskTransitionError,
+    TaskType,
+};
+pub use task_queue::{
+    Context, EnqueueOutcome, ExecutionEvent, InMemoryBackend, QueueError, Queueable, RetainedTasks,
+    RetentionMode, RunnableTask, SchedulerCommand, SqlBackend, TaskError, TaskQueue, UniqueIndex,
+    Worker,
+};
@@ -4,7 +4,7 @@ use std::{fmt::Display, time::SystemTime};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{JobId, TaskId};
+use crate::{JobId, RunnableTask, TaskId};
 
 /// Execution outcome reported by workers
 #[derive(Debug)]
@@ -14,6 +14,35 @@ pub enum TaskResult {
     FatalFailure(String),
 }
 
+/// Names the category of work a [`Task`] belongs to, so a `Worker` can be
+/// scoped to only the types it knows how to run (e.g. CPU-heavy vs. IO-heavy
+/// pools against one logical queue).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TaskType(String);
+
+impl TaskType {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    /// The type every `Task` falls into unless given one explicitly.
+    pub fn common() -> Self {
+        Self("common".to_string())
+    }
+}
+
+impl Default for TaskType {
+    fn default() -> Self {
+        Self::common()
+    }
+}
+
+impl Display for TaskType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Backoff strategy by the scheduler
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BackoffStrategy {
@@ -23,6 +52,14 @@ pub enum BackoffStrategy {
         factor: u32,
         max_delay: std::time::Duration,
     },
+    /// AWS-style decorrelated jitter: `next = clamp(base, prev * factor, max_delay)`,
+    /// chosen uniformly at random, with `prev` seeded to `base` on the first attempt.
+    /// Spreads out retries that would otherwise stampede a downstream in lockstep.
+    ExponentialJitter {
+        base: std::time::Duration,
+        factor: u32,
+        max_delay: std::time::Duration,
+    },
 }
 
 /// Retry policy interpreted exclusively by the scheduler
@@ -76,29 +113,42 @@ impl TaskStatus {
 }
 
 /// Task domain object
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Task {
     pub id: TaskId,
     pub job_id: JobId,
-    pub payload: Vec<u8>,
+    pub payload: Box<dyn RunnableTask>,
+    pub task_type: TaskType,
 
     pub status: TaskStatus,
     pub attempts: u32,
     pub retry_policy: Option<RetryPolicy>,
+    /// Delay used for the most recent retry, read back by
+    /// [`BackoffStrategy::ExponentialJitter`] to decorrelate the next one.
+    pub last_backoff: Option<std::time::Duration>,
+    /// Identifies this task for the purposes of `TaskQueue::enqueue_unique`:
+    /// two tasks sharing a `uniq_hash` are treated as duplicates while either
+    /// is pending or running. Holds a caller-supplied key, if any; when
+    /// `None`, `enqueue_unique` uses a SHA-256 over `task_type` + `payload`
+    /// instead, without storing it here.
+    pub uniq_hash: Option<String>,
 
     pub created_at: SystemTime,
     pub updated_at: SystemTime,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TaskBuilder {
     pub id: Option<TaskId>,
     pub job_id: Option<JobId>,
-    pub payload: Vec<u8>,
+    pub payload: Box<dyn RunnableTask>,
+    pub task_type: Option<TaskType>,
 
     pub status: Option<TaskStatus>,
     pub attempts: Option<u32>,
     pub retry_policy: Option<RetryPolicy>,
+    pub last_backoff: Option<std::time::Duration>,
+    pub uniq_hash: Option<String>,
 
     pub created_at: Option<SystemTime>,
     pub updated_at: Option<SystemTime>,
@@ -114,10 +164,19 @@ pub enum TaskTransitionError {
 }
 
 impl Task {
-    pub fn new(payload: Vec<u8>) -> TaskBuilder {
+    pub fn new(payload: Box<dyn RunnableTask>) -> TaskBuilder {
         TaskBuilder {
+            id: None,
+            job_id: None,
             payload,
-            ..Default::default()
+            task_type: None,
+            status: None,
+            attempts: None,
+            retry_policy: None,
+            last_backoff: None,
+            uniq_hash: None,
+            created_at: None,
+            updated_at: None,
         }
     }
 
@@ -189,6 +248,11 @@ impl TaskBuilder {
         self
     }
 
+    pub fn task_type(mut self, task_type: TaskType) -> Self {
+        self.task_type = Some(task_type);
+        self
+    }
+
     pub fn status(mut self, status: TaskStatus) -> Self {
         self.status = Some(status);
         self
@@ -204,6 +268,13 @@ impl TaskBuilder {
         self
     }
 
+    /// Sets an explicit uniqueness key. Without one, `TaskQueue::enqueue_unique`
+    /// derives it from `task_type` and `payload`.
+    pub fn uniq_hash(mut self, uniq_hash: impl Into<String>) -> Self {
+        self.uniq_hash = Some(uniq_hash.into());
+        self
+    }
+
     pub fn created_at(mut self, created_at: SystemTime) -> Self {
         self.created_at = Some(created_at);
         self
@@ -217,12 +288,15 @@ impl TaskBuilder {
     pub fn build(self) -> Task {
         let now = SystemTime::now();
         Task {
-            id: self.id.unwrap_or_default(),
-            job_id: self.job_id.unwrap_or_default(),
+            id: self.id.unwrap_or_else(TaskId::new),
+            job_id: self.job_id.unwrap_or_else(JobId::new),
             payload: self.payload,
+            task_type: self.task_type.unwrap_or_default(),
             status: self.status.unwrap_or(TaskStatus::Pending),
             attempts: self.attempts.unwrap_or_default(),
             retry_policy: self.retry_policy,
+            last_backoff: self.last_backoff,
+            uniq_hash: self.uniq_hash,
             created_at: self.created_at.unwrap_or(now),
             updated_at: self.created_at.unwrap_or(now),
         }
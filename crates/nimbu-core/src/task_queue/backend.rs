@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::{Task, TaskId, TaskType, task_queue::errors::QueueError};
+
+/// Storage abstraction behind [`TaskQueue`](crate::TaskQueue).
+///
+/// A `Queueable` owns where tasks actually live: purely in memory, or in a
+/// durable store that survives a process restart. `TaskQueue` itself only
+/// ever talks to this trait.
+#[async_trait]
+pub trait Queueable: Send + Sync + std::fmt::Debug {
+    /// Records a task as pending and ready to be claimed.
+    async fn enqueue(&self, task: Task) -> Result<(), QueueError>;
+
+    /// Claims the next ready task, if any. When `task_type` is given, only a
+    /// task of that type is returned, leaving others for other workers;
+    /// without one, only a task of the default `"common"` type is returned.
+    async fn claim(&self, task_type: Option<&TaskType>) -> Option<Task>;
+
+    /// Marks a task as completed in storage.
+    async fn mark_completed(&self, id: &TaskId) -> Result<(), QueueError>;
+
+    /// Marks a task as permanently failed in storage, e.g. after its retries
+    /// are exhausted or a [`TaskResult::FatalFailure`](crate::TaskResult).
+    /// Implementations must exclude these tasks from `reload_pending`.
+    async fn mark_failed_permanent(&self, id: &TaskId) -> Result<(), QueueError>;
+
+    /// Persists a task that is either being retried after a failure or
+    /// delayed for a one-shot future run, alongside the delay before it
+    /// should become ready again.
+    async fn schedule_retry(&self, task: &Task, delay: Duration) -> Result<(), QueueError>;
+
+    /// Called once at startup: returns every task that has not reached a
+    /// terminal state, paired with the delay (if any) remaining before it
+    /// should run.
+    async fn reload_pending(&self) -> Vec<(Task, Option<Duration>)>;
+}
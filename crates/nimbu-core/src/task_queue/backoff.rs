@@ -1,12 +1,18 @@
 use std::time::Duration;
 
+use rand::Rng;
+
 use crate::{BackoffStrategy, Task};
 
-pub fn compute_backoff(task: &Task) -> Option<Duration> {
+/// Computes the delay before `task`'s next retry attempt, per its
+/// `retry_policy`. Whatever the strategy, the result is also stored in
+/// `task.last_backoff`, which [`BackoffStrategy::ExponentialJitter`] reads
+/// back to decorrelate the next delay (AWS-style "decorrelated jitter").
+pub fn compute_backoff(task: &mut Task) -> Option<Duration> {
     let policy = task.retry_policy.as_ref()?;
 
-    match &policy.strategy {
-        BackoffStrategy::Fixed(delay) => Some(*delay),
+    let delay = match &policy.strategy {
+        BackoffStrategy::Fixed(delay) => *delay,
 
         BackoffStrategy::Exponential {
             base,
@@ -14,8 +20,34 @@ pub fn compute_backoff(task: &Task) -> Option<Duration> {
             max_delay,
         } => {
             let exp = factor.saturating_pow(task.attempts);
-            let delay = base.saturating_mul(exp);
-            Some(delay.min(*max_delay))
+            base.saturating_mul(exp).min(*max_delay)
+        }
+
+        BackoffStrategy::ExponentialJitter {
+            base,
+            factor,
+            max_delay,
+        } => {
+            let prev = task.last_backoff.unwrap_or(*base);
+            let upper = prev.saturating_mul(*factor).max(*base);
+            random_between(*base, upper).min(*max_delay)
         }
+    };
+
+    task.last_backoff = Some(delay);
+    Some(delay)
+}
+
+/// A uniformly random duration in `[low, high]`, clamped to `low` if the
+/// range is empty.
+fn random_between(low: Duration, high: Duration) -> Duration {
+    if high <= low {
+        return low;
     }
+
+    let low_ms = low.as_millis() as u64;
+    let high_ms = high.as_millis() as u64;
+    let ms = rand::thread_rng().gen_range(low_ms..=high_ms);
+
+    Duration::from_millis(ms)
 }
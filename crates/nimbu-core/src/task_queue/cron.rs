@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use cron::Schedule;
+
+use crate::{RunnableTask, Task, TaskBuilder, TaskId};
+
+/// Produces a fresh `Task` from a cron template: a new id with attempts,
+/// status, `last_backoff` and timestamps reset, carrying over the job, task
+/// type, retry policy, `uniq_hash`, and a freshly deserialized copy of the
+/// payload.
+pub(crate) fn retemplate(template: &Task) -> Option<Task> {
+    let value = serde_json::to_value(&*template.payload).ok()?;
+    let payload: Box<dyn RunnableTask> = serde_json::from_value(value).ok()?;
+
+    Some(
+        TaskBuilder {
+            id: Some(TaskId::new()),
+            job_id: Some(template.job_id.clone()),
+            payload,
+            task_type: Some(template.task_type.clone()),
+            status: None,
+            attempts: None,
+            retry_policy: template.retry_policy.clone(),
+            last_backoff: None,
+            uniq_hash: template.uniq_hash.clone(),
+            created_at: None,
+            updated_at: None,
+        }
+        .build(),
+    )
+}
+
+/// Delay until `schedule`'s next occurrence, or `None` if the schedule has
+/// no further occurrences.
+pub(crate) fn next_delay(schedule: &Schedule) -> Option<Duration> {
+    let now = chrono::Utc::now();
+    let next = schedule.after(&now).next()?;
+
+    (next - now).to_std().ok()
+}
@@ -6,3 +6,13 @@ pub enum QueueError {
     #[error("queue is closed")]
     Closed,
 }
+
+/// Outcome a [`RunnableTask`](crate::RunnableTask) reports from `run`.
+#[derive(Debug, thiserror::Error)]
+pub enum TaskError {
+    #[error("retryable task failure: {0}")]
+    Retryable(String),
+
+    #[error("fatal task failure: {0}")]
+    Fatal(String),
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::{Mutex, RwLock, mpsc};
+
+use crate::{
+    Task, TaskId, TaskType,
+    task_queue::{backend::Queueable, errors::QueueError},
+};
+
+#[derive(Debug, Clone)]
+struct Channel {
+    tx: mpsc::Sender<Task>,
+    rx: Arc<Mutex<mpsc::Receiver<Task>>>,
+}
+
+/// The original channel-backed queue, kept around as the default
+/// [`Queueable`] implementation. Tasks are routed into one bounded channel
+/// per [`TaskType`], created lazily on first use. Nothing here survives a
+/// process restart.
+#[derive(Debug)]
+pub struct InMemoryBackend {
+    capacity: usize,
+    channels: RwLock<HashMap<TaskType, Channel>>,
+}
+
+impl InMemoryBackend {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            channels: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn channel_for(&self, task_type: &TaskType) -> Channel {
+        if let Some(channel) = self.channels.read().await.get(task_type) {
+            return channel.clone();
+        }
+
+        let mut channels = self.channels.write().await;
+        channels
+            .entry(task_type.clone())
+            .or_insert_with(|| {
+                let (tx, rx) = mpsc::channel(self.capacity);
+                Channel {
+                    tx,
+                    rx: Arc::new(Mutex::new(rx)),
+                }
+            })
+            .clone()
+    }
+}
+
+#[async_trait]
+impl Queueable for InMemoryBackend {
+    async fn enqueue(&self, task: Task) -> Result<(), QueueError> {
+        let channel = self.channel_for(&task.task_type).await;
+        channel.tx.send(task).await.map_err(|_| QueueError::Closed)
+    }
+
+    async fn claim(&self, task_type: Option<&TaskType>) -> Option<Task> {
+        let task_type = task_type.cloned().unwrap_or_default();
+        let channel = self.channel_for(&task_type).await;
+
+        let mut rx = channel.rx.lock().await;
+        rx.recv().await
+    }
+
+    async fn mark_completed(&self, _id: &TaskId) -> Result<(), QueueError> {
+        // completed tasks are simply dropped; there is nothing further to persist
+        Ok(())
+    }
+
+    async fn mark_failed_permanent(&self, _id: &TaskId) -> Result<(), QueueError> {
+        // permanently-failed tasks are simply dropped; there is nothing further to persist
+        Ok(())
+    }
+
+    async fn schedule_retry(&self, _task: &Task, _delay: Duration) -> Result<(), QueueError> {
+        // the scheduler's own `DelayQueue` holds the task during the backoff
+        // window, so the in-memory backend has nothing further to do
+        Ok(())
+    }
+
+    async fn reload_pending(&self) -> Vec<(Task, Option<Duration>)> {
+        // in-memory state never survives a restart, so there is nothing to reload
+        Vec::new()
+    }
+}
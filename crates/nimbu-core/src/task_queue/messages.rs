@@ -11,7 +11,18 @@ pub enum ExecutionEvent {
 
 #[derive(Debug)]
 pub enum SchedulerCommand {
-    Schedule { task: Task, delay: Duration },
+    Schedule {
+        task: Task,
+        delay: Duration,
+    },
+    /// Registers a recurring task: `task_template` is cloned into a fresh
+    /// `Task` (new id, reset attempts/status) each time `schedule` fires.
+    /// Boxed so this variant doesn't blow up `SchedulerCommand`'s size for
+    /// the far more common `Schedule`/`ExecutionResult` commands.
+    ScheduleCron {
+        task_template: Box<Task>,
+        schedule: Box<cron::Schedule>,
+    },
     ExecutionResult(ExecutionEvent),
     Shutdown,
 }
@@ -0,0 +1,24 @@
+pub mod backend;
+pub mod backoff;
+pub mod cron;
+pub mod errors;
+pub mod memory_backend;
+pub mod messages;
+pub mod queue;
+pub mod retention;
+pub mod runnable;
+pub mod scheduler;
+pub mod sql_backend;
+pub mod uniqueness;
+pub mod worker;
+
+pub use backend::Queueable;
+pub use errors::{QueueError, TaskError};
+pub use memory_backend::InMemoryBackend;
+pub use messages::{ExecutionEvent, SchedulerCommand};
+pub use queue::{EnqueueOutcome, TaskQueue};
+pub use retention::{RetainedTasks, RetentionMode};
+pub use runnable::{Context, RunnableTask};
+pub use sql_backend::SqlBackend;
+pub use uniqueness::UniqueIndex;
+pub use worker::Worker;
@@ -1,45 +1,84 @@
-use std::{
-    sync::atomic::{AtomicUsize, Ordering},
-    time::Duration,
+use std::str::FromStr;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
 };
+use std::time::Duration;
 
-use tokio::{
-    sync::{Mutex, mpsc},
-    task::JoinHandle,
-};
+use cron::Schedule;
+use tokio::{sync::mpsc, task::JoinHandle};
 use tracing::{debug, info};
 
 use crate::{
-    Task,
-    task_queue::{messages::SchedulerCommand, scheduler::scheduler_loop},
+    Task, TaskId, TaskStatus, TaskType,
+    task_queue::{
+        backend::Queueable, memory_backend::InMemoryBackend, messages::SchedulerCommand,
+        retention::{RetainedTasks, RetentionMode},
+        scheduler::scheduler_loop,
+        uniqueness::{UniqueIndex, derive_uniq_hash},
+    },
 };
 
+/// Result of `TaskQueue::enqueue_unique`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnqueueOutcome {
+    /// No live task shared this uniqueness key; `task` was enqueued.
+    Enqueued,
+    /// Another pending/running task already holds this uniqueness key;
+    /// `task` was dropped in its favor.
+    Duplicate(TaskId),
+}
+
 #[derive(Debug)]
 pub struct TaskQueue {
-    pub ready_tx: mpsc::Sender<Task>,
-    pub ready_rx: Mutex<mpsc::Receiver<Task>>,
+    backend: Arc<dyn Queueable>,
+    retained: RetainedTasks,
+    unique: UniqueIndex,
 
     pub scheduler_tx: mpsc::Sender<SchedulerCommand>,
     pub scheduler_handle: JoinHandle<()>,
 
-    pub len: AtomicUsize,
-    pub capacity: usize,
+    len: AtomicUsize,
+    capacity: usize,
 }
 
 impl TaskQueue {
+    /// Builds a queue backed by the default in-process channel backend,
+    /// dropping finished tasks ([`RetentionMode::RemoveAll`]).
     pub fn new(capacity: usize) -> Self {
+        Self::with_backend(
+            Arc::new(InMemoryBackend::new(capacity)),
+            capacity,
+            RetentionMode::default(),
+        )
+    }
+
+    /// Builds a queue on top of a custom [`Queueable`] backend, e.g. a
+    /// persistent SQL-backed one, with the given finished-task retention.
+    pub fn with_backend(
+        backend: Arc<dyn Queueable>,
+        capacity: usize,
+        retention: RetentionMode,
+    ) -> Self {
         info!(capacity, "initializing task queue");
 
-        let (ready_tx, ready_rx) = mpsc::channel(capacity);
         let (scheduler_tx, scheduler_rx) = mpsc::channel(1024);
+        let retained = RetainedTasks::new(retention);
+        let unique = UniqueIndex::new();
 
-        let scheduler_handle = tokio::spawn(scheduler_loop(ready_tx.clone(), scheduler_rx));
+        let scheduler_handle = tokio::spawn(scheduler_loop(
+            backend.clone(),
+            scheduler_rx,
+            retained.clone(),
+            unique.clone(),
+        ));
 
         info!(capacity, "task queue initialized");
 
         Self {
-            ready_tx,
-            ready_rx: Mutex::new(ready_rx),
+            backend,
+            retained,
+            unique,
             scheduler_tx,
             scheduler_handle,
             len: AtomicUsize::new(0),
@@ -47,12 +86,35 @@ impl TaskQueue {
         }
     }
 
+    /// Looks up the last known status of a task, if the queue's
+    /// [`RetentionMode`] kept it after reaching a terminal state.
+    pub async fn status(&self, id: &TaskId) -> Option<TaskStatus> {
+        self.retained.status(id).await
+    }
+
     pub async fn enqueue(&self, task: Task) -> Result<(), ()> {
-        self.ready_tx.send(task).await.map_err(|_| ())?;
+        self.backend.enqueue(task).await.map_err(|_| ())?;
         self.len.fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
 
+    /// Enqueues `task` unless another task with the same uniqueness key is
+    /// already pending or running. The key is `task.uniq_hash` if set,
+    /// otherwise a hash derived from `task_type` and `payload`.
+    pub async fn enqueue_unique(&self, task: Task) -> Result<EnqueueOutcome, ()> {
+        let Some(hash) = task.uniq_hash.clone().or_else(|| derive_uniq_hash(&task)) else {
+            self.enqueue(task).await?;
+            return Ok(EnqueueOutcome::Enqueued);
+        };
+
+        if let Err(existing) = self.unique.try_reserve(&hash, &task.id).await {
+            return Ok(EnqueueOutcome::Duplicate(existing));
+        }
+
+        self.enqueue(task).await?;
+        Ok(EnqueueOutcome::Enqueued)
+    }
+
     pub fn enqueue_delayed(&self, task: Task, delay: Duration) {
         debug!(
             task_id = ?task.id,
@@ -65,9 +127,26 @@ impl TaskQueue {
             .try_send(SchedulerCommand::Schedule { task, delay });
     }
 
-    pub async fn dequeue(&self) -> Option<Task> {
-        let mut rx = self.ready_rx.lock().await;
-        let task = rx.recv().await;
+    /// Registers a recurring task: on every occurrence of `expression`
+    /// (`cron` crate syntax, with a leading seconds field, e.g.
+    /// `"0 */5 * * * *"`), `task_template` is cloned into a fresh task
+    /// with a new id and reset attempts/status.
+    pub fn enqueue_cron(&self, task_template: Task, expression: &str) -> Result<(), cron::error::Error> {
+        let schedule = Schedule::from_str(expression)?;
+
+        let _ = self.scheduler_tx.try_send(SchedulerCommand::ScheduleCron {
+            task_template: Box::new(task_template),
+            schedule: Box::new(schedule),
+        });
+
+        Ok(())
+    }
+
+    /// Claims the next ready task. When `task_type` is given, only a task
+    /// of that type is returned; tasks of other types are left for other
+    /// workers.
+    pub async fn dequeue(&self, task_type: Option<&TaskType>) -> Option<Task> {
+        let task = self.backend.claim(task_type).await;
 
         if task.is_some() {
             self.len.fetch_sub(1, Ordering::SeqCst);
@@ -76,6 +155,18 @@ impl TaskQueue {
         task
     }
 
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub async fn shutdown(self) {
         info!("task queue shutdown initiated");
 
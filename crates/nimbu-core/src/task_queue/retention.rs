@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+
+use crate::{Task, TaskId, TaskStatus};
+
+/// Controls what happens to a task once it reaches a terminal
+/// [`TaskStatus`] (`Completed` or `FailedPermanent`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetentionMode {
+    /// Drop every finished task; nothing can be inspected afterward.
+    #[default]
+    RemoveAll,
+
+    /// Keep only tasks that failed permanently.
+    KeepFailed,
+
+    /// Keep every finished task, completed or failed.
+    KeepAll,
+}
+
+/// Keeps finished tasks around per a [`RetentionMode`], so outcomes can be
+/// inspected after the fact via `TaskQueue::status`.
+#[derive(Debug, Clone)]
+pub struct RetainedTasks {
+    mode: RetentionMode,
+    tasks: Arc<RwLock<HashMap<TaskId, TaskStatus>>>,
+}
+
+impl RetainedTasks {
+    pub fn new(mode: RetentionMode) -> Self {
+        Self {
+            mode,
+            tasks: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Records a task's outcome if `mode` calls for keeping it.
+    pub async fn record(&self, task: &Task) {
+        let keep = match self.mode {
+            RetentionMode::RemoveAll => false,
+            RetentionMode::KeepFailed => matches!(task.status, TaskStatus::FailedPermanent { .. }),
+            RetentionMode::KeepAll => true,
+        };
+
+        if keep {
+            self.tasks
+                .write()
+                .await
+                .insert(task.id.clone(), task.status.clone());
+        }
+    }
+
+    pub async fn status(&self, id: &TaskId) -> Option<TaskStatus> {
+        self.tasks.read().await.get(id).cloned()
+    }
+}
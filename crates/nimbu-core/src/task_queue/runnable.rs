@@ -0,0 +1,69 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::{TaskId, task_queue::errors::TaskError, task_queue::queue::TaskQueue};
+
+/// Context handed to a task's [`RunnableTask::run`] implementation.
+///
+/// `state` is the application's shared resources (DB pools, HTTP clients,
+/// config, ...), set up once and handed to every task; `queue` lets a task
+/// enqueue follow-up work. `RunnableTask` is `typetag`-serialized, which
+/// rules out a generic `run` signature, so `state` is carried type-erased
+/// and recovered with [`Context::state`].
+#[derive(Clone)]
+pub struct Context {
+    pub task_id: TaskId,
+    pub queue: Arc<TaskQueue>,
+    state: Arc<dyn Any + Send + Sync>,
+}
+
+impl Context {
+    pub fn new(task_id: TaskId, queue: Arc<TaskQueue>, state: Arc<dyn Any + Send + Sync>) -> Self {
+        Self {
+            task_id,
+            queue,
+            state,
+        }
+    }
+
+    /// Recovers the shared application state as `S`, the type the
+    /// [`Worker`](crate::Worker) pool was constructed with.
+    pub fn state<S: Send + Sync + 'static>(&self) -> Option<&S> {
+        self.state.downcast_ref::<S>()
+    }
+}
+
+impl fmt::Debug for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("task_id", &self.task_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The unit of work a [`Task`](crate::Task) carries as its payload.
+///
+/// Implementations are registered with `typetag` so a `Task`'s payload
+/// round-trips through serialization as the concrete type that produced it,
+/// the same way backie's `RunnableTask`/`AsyncRunnable` do.
+#[async_trait]
+#[typetag::serde(tag = "type")]
+pub trait RunnableTask: Send + Sync + std::fmt::Debug {
+    async fn run(&self, ctx: &Context) -> Result<(), TaskError>;
+
+    /// Maximum number of retries for this task. The scheduler uses it only
+    /// when the enclosing `Task` carries no `RetryPolicy`.
+    fn max_retries(&self) -> u32 {
+        5
+    }
+
+    /// Delay to wait before the given retry attempt. Like `max_retries`, used
+    /// only when the enclosing `Task` carries no `RetryPolicy`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        Duration::from_secs(2u64.saturating_pow(attempt))
+    }
+}
@@ -1,34 +1,75 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use futures_util::StreamExt;
 use tokio::sync::mpsc;
 use tokio_util::time::DelayQueue;
 use tracing::{error, info, warn};
+use uuid::Uuid;
 
 use crate::{
-    Task,
+    Task, TaskStatus,
     task_queue::{
+        backend::Queueable,
         backoff::compute_backoff,
+        cron::{next_delay, retemplate},
         messages::{ExecutionEvent, SchedulerCommand},
+        retention::RetainedTasks,
+        uniqueness::{UniqueIndex, derive_uniq_hash},
     },
 };
 
+/// A registered recurring job: the template cloned into a fresh task on
+/// every fire, plus the cron expression driving it.
+struct CronJob {
+    template: Task,
+    schedule: cron::Schedule,
+}
+
 pub async fn scheduler_loop(
-    ready_tx: mpsc::Sender<Task>,
+    backend: Arc<dyn Queueable>,
     mut cmd_rx: mpsc::Receiver<SchedulerCommand>,
+    retained: RetainedTasks,
+    unique: UniqueIndex,
 ) {
     let mut delay_queue = DelayQueue::<Task>::new();
+    let mut cron_jobs: HashMap<Uuid, CronJob> = HashMap::new();
+    let mut cron_ticks = DelayQueue::<Uuid>::new();
 
     info!("delay scheduler started");
 
+    for (task, delay) in backend.reload_pending().await {
+        match delay {
+            Some(delay) => {
+                delay_queue.insert(task, delay);
+            }
+            None => {
+                let _ = backend.enqueue(task).await;
+            }
+        }
+    }
+
     loop {
         tokio::select! {
             Some(cmd) = cmd_rx.recv() => {
                 match cmd {
                     SchedulerCommand::Schedule { task, delay } => {
+                        let _ = backend.schedule_retry(&task, delay).await;
                         delay_queue.insert(task, delay);
                     }
 
+                    SchedulerCommand::ScheduleCron { task_template, schedule } => {
+                        if let Some(delay) = next_delay(&schedule) {
+                            let id = Uuid::new_v4();
+                            cron_ticks.insert(id, delay);
+                            cron_jobs.insert(id, CronJob { template: *task_template, schedule: *schedule });
+                        } else {
+                            warn!("cron schedule has no upcoming occurrences; ignoring");
+                        }
+                    }
+
                     SchedulerCommand::ExecutionResult(event) => {
-                        handle_execution_event(event, &mut delay_queue);
+                        handle_execution_event(event, &mut delay_queue, &backend, &retained, &unique).await;
                     }
 
                     SchedulerCommand::Shutdown => break,
@@ -37,7 +78,27 @@ pub async fn scheduler_loop(
 
             Some(expired) = delay_queue.next() => {
                 let task = expired.into_inner();
-                let _ = ready_tx.send(task).await;
+                let _ = backend.enqueue(task).await;
+            }
+
+            Some(expired) = cron_ticks.next() => {
+                let id = expired.into_inner();
+                if let Some(job) = cron_jobs.get(&id) {
+                    if let Some(task) = retemplate(&job.template) {
+                        let _ = backend.enqueue(task).await;
+                    } else {
+                        error!("failed to materialize task from cron template");
+                    }
+
+                    match next_delay(&job.schedule) {
+                        Some(delay) => {
+                            cron_ticks.insert(id, delay);
+                        }
+                        None => {
+                            cron_jobs.remove(&id);
+                        }
+                    }
+                }
             }
 
             else => {
@@ -49,31 +110,59 @@ pub async fn scheduler_loop(
     info!("delay scheduler exited");
 }
 
-fn handle_execution_event(event: ExecutionEvent, delay_queue: &mut DelayQueue<Task>) {
+async fn handle_execution_event(
+    event: ExecutionEvent,
+    delay_queue: &mut DelayQueue<Task>,
+    backend: &Arc<dyn Queueable>,
+    retained: &RetainedTasks,
+    unique: &UniqueIndex,
+) {
     match event {
         ExecutionEvent::Completed(mut task) => {
             let _ = task.complete();
+            let _ = backend.mark_completed(&task.id).await;
             info!(
                 task_id = ?task.id,
                 "task completed successfully"
             );
+            release_uniq_hash(&task, unique).await;
+            retained.record(&task).await;
         }
 
         ExecutionEvent::RetryableFailure(mut task, error) => {
             task.mark_retryable_failure(error);
 
-            if let Some(policy) = &task.retry_policy {
-                if policy.can_retry(task.attempts) {
-                    if let Some(delay) = compute_backoff(&task) {
-                        warn!(
-                            task_id = ?task.id,
-                            attempts = task.attempts,
-                            delay_ms = delay.as_millis(),
-                            "scheduling retry"
-                        );
-                        delay_queue.insert(task, delay);
-                        return;
-                    }
+            // A `retry_policy` on the task takes precedence; absent one,
+            // fall back to the payload's own `max_retries`/`backoff` so a
+            // plain `Task::new(payload).build()` still retries.
+            let can_retry = match &task.retry_policy {
+                Some(policy) => policy.can_retry(task.attempts),
+                None => task.attempts < task.payload.max_retries(),
+            };
+
+            if can_retry {
+                let delay = if task.retry_policy.is_some() {
+                    compute_backoff(&mut task)
+                } else {
+                    Some(task.payload.backoff(task.attempts))
+                };
+
+                if let Some(delay) = delay {
+                    warn!(
+                        task_id = ?task.id,
+                        attempts = task.attempts,
+                        delay_ms = delay.as_millis(),
+                        "scheduling retry"
+                    );
+                    let _ = backend.schedule_retry(&task, delay).await;
+
+                    // `schedule_retry` above needs `task.status` to still
+                    // carry the `Failed` error message it was just set to;
+                    // reset it to `Pending` now so the task re-enters the
+                    // ready path cleanly once its delay elapses.
+                    task.status = TaskStatus::Pending;
+                    delay_queue.insert(task, delay);
+                    return;
                 }
             }
 
@@ -82,6 +171,9 @@ fn handle_execution_event(event: ExecutionEvent, delay_queue: &mut DelayQueue<Ta
                 task_id = ?task.id,
                 "task permanently failed"
             );
+            let _ = backend.mark_failed_permanent(&task.id).await;
+            release_uniq_hash(&task, unique).await;
+            retained.record(&task).await;
         }
 
         ExecutionEvent::FatalFailure(mut task, error) => {
@@ -90,6 +182,17 @@ fn handle_execution_event(event: ExecutionEvent, delay_queue: &mut DelayQueue<Ta
                 task_id = ?task.id,
                 "fatal task failure"
             );
+            let _ = backend.mark_failed_permanent(&task.id).await;
+            release_uniq_hash(&task, unique).await;
+            retained.record(&task).await;
         }
     }
 }
+
+/// Frees `task`'s uniqueness key, if any, now that it's reached a terminal
+/// status — letting a future duplicate through again.
+async fn release_uniq_hash(task: &Task, unique: &UniqueIndex) {
+    if let Some(hash) = task.uniq_hash.clone().or_else(|| derive_uniq_hash(task)) {
+        unique.release(&hash, &task.id).await;
+    }
+}
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use tracing::error;
+
+use crate::{
+    JobId, Task, TaskBuilder, TaskId, TaskStatus, TaskType,
+    task_queue::{backend::Queueable, errors::QueueError},
+};
+
+/// Persists tasks in a `nimbu_tasks` table so pending, delayed, and
+/// retrying work survives a process restart. Mirrors the `fang_tasks` /
+/// backie task table shape: one row per task with its own `scheduled_at`
+/// and `retries` columns.
+///
+/// Expects a table shaped like:
+///
+/// ```sql
+/// CREATE TABLE nimbu_tasks (
+///     id          TEXT PRIMARY KEY,
+///     job_id      TEXT NOT NULL,
+///     task_type   TEXT NOT NULL,
+///     payload     JSONB NOT NULL,
+///     status      TEXT NOT NULL, -- 'pending' | 'running' | 'completed' | 'failed_permanent'
+///     attempts    INTEGER NOT NULL DEFAULT 0,
+///     scheduled_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+///     retries     INTEGER NOT NULL DEFAULT 0,
+///     error       TEXT,
+///     uniq_hash   TEXT
+/// );
+/// ```
+#[derive(Debug)]
+pub struct SqlBackend {
+    pool: PgPool,
+}
+
+impl SqlBackend {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_task(row: &sqlx::postgres::PgRow) -> Result<Task, QueueError> {
+        let id: String = row.try_get("id").map_err(|_| QueueError::Closed)?;
+        let job_id: String = row.try_get("job_id").map_err(|_| QueueError::Closed)?;
+        let task_type: String = row.try_get("task_type").map_err(|_| QueueError::Closed)?;
+        let payload: serde_json::Value = row.try_get("payload").map_err(|_| QueueError::Closed)?;
+        let attempts: i32 = row.try_get("attempts").map_err(|_| QueueError::Closed)?;
+        let uniq_hash: Option<String> = row.try_get("uniq_hash").ok();
+
+        let payload = serde_json::from_value(payload).map_err(|err| {
+            error!(%err, "failed to deserialize persisted task payload");
+            QueueError::Closed
+        })?;
+
+        Ok(TaskBuilder {
+            id: Some(TaskId::from_raw(id)),
+            job_id: Some(JobId::from_raw(job_id)),
+            payload,
+            task_type: Some(TaskType::new(task_type)),
+            status: Some(TaskStatus::Pending),
+            attempts: Some(attempts.max(0) as u32),
+            retry_policy: None,
+            last_backoff: None,
+            uniq_hash,
+            created_at: None,
+            updated_at: None,
+        }
+        .build())
+    }
+}
+
+#[async_trait]
+impl Queueable for SqlBackend {
+    async fn enqueue(&self, task: Task) -> Result<(), QueueError> {
+        let payload = serde_json::to_value(&task.payload).map_err(|_| QueueError::Closed)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO nimbu_tasks (id, job_id, task_type, payload, status, attempts, scheduled_at, retries, error, uniq_hash)
+            VALUES ($1, $2, $3, $4, 'pending', $5, now(), 0, NULL, $6)
+            ON CONFLICT (id) DO UPDATE SET status = 'pending', scheduled_at = now()
+            "#,
+        )
+        .bind(task.id.to_string())
+        .bind(task.job_id.to_string())
+        .bind(task.task_type.to_string())
+        .bind(payload)
+        .bind(task.attempts as i32)
+        .bind(&task.uniq_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| {
+            error!(%err, "failed to enqueue task");
+            QueueError::Closed
+        })?;
+
+        Ok(())
+    }
+
+    async fn claim(&self, task_type: Option<&TaskType>) -> Option<Task> {
+        // An unscoped claim only ever drains the "common" type, same as
+        // `InMemoryBackend`, so a dedicated pool's tasks aren't stolen by a
+        // worker that didn't ask for them.
+        let task_type = task_type.cloned().unwrap_or_default().to_string();
+
+        let row = sqlx::query(
+            r#"
+            UPDATE nimbu_tasks
+            SET status = 'running'
+            WHERE id = (
+                SELECT id FROM nimbu_tasks
+                WHERE status = 'pending'
+                    AND scheduled_at <= now()
+                    AND task_type = $1
+                ORDER BY scheduled_at
+                FOR UPDATE SKIP LOCKED
+                LIMIT 1
+            )
+            RETURNING id, job_id, task_type, payload, attempts, uniq_hash
+            "#,
+        )
+        .bind(task_type)
+        .fetch_optional(&self.pool)
+        .await
+        .ok()
+        .flatten()?;
+
+        Self::row_to_task(&row).ok()
+    }
+
+    async fn mark_completed(&self, id: &TaskId) -> Result<(), QueueError> {
+        sqlx::query("UPDATE nimbu_tasks SET status = 'completed' WHERE id = $1")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                error!(%err, "failed to mark task completed");
+                QueueError::Closed
+            })?;
+
+        Ok(())
+    }
+
+    async fn mark_failed_permanent(&self, id: &TaskId) -> Result<(), QueueError> {
+        sqlx::query("UPDATE nimbu_tasks SET status = 'failed_permanent' WHERE id = $1")
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                error!(%err, "failed to mark task permanently failed");
+                QueueError::Closed
+            })?;
+
+        Ok(())
+    }
+
+    async fn schedule_retry(&self, task: &Task, delay: Duration) -> Result<(), QueueError> {
+        let payload = serde_json::to_value(&task.payload).map_err(|_| QueueError::Closed)?;
+        let error = match &task.status {
+            TaskStatus::Failed { error, .. } => error.as_str(),
+            _ => "",
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO nimbu_tasks (id, job_id, task_type, payload, status, attempts, scheduled_at, retries, error)
+            VALUES ($1, $2, $3, $4, 'pending', $5, now() + ($6 || ' milliseconds')::interval, $5, $7)
+            ON CONFLICT (id) DO UPDATE SET
+                status = 'pending',
+                attempts = $5,
+                scheduled_at = now() + ($6 || ' milliseconds')::interval,
+                retries = $5,
+                error = $7
+            "#,
+        )
+        .bind(task.id.to_string())
+        .bind(task.job_id.to_string())
+        .bind(task.task_type.to_string())
+        .bind(payload)
+        .bind(task.attempts as i32)
+        .bind(delay.as_millis().to_string())
+        .bind(error)
+        .execute(&self.pool)
+        .await
+        .map_err(|err| {
+            error!(%err, "failed to schedule task retry");
+            QueueError::Closed
+        })?;
+
+        Ok(())
+    }
+
+    async fn reload_pending(&self) -> Vec<(Task, Option<Duration>)> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, job_id, task_type, payload, attempts, scheduled_at, uniq_hash
+            FROM nimbu_tasks
+            WHERE status NOT IN ('completed', 'failed_permanent')
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        rows.iter()
+            .filter_map(|row| {
+                let task = Self::row_to_task(row).ok()?;
+                let scheduled_at: chrono::DateTime<chrono::Utc> = row.try_get("scheduled_at").ok()?;
+                let now = chrono::Utc::now();
+                let delay = (scheduled_at > now)
+                    .then(|| (scheduled_at - now).to_std().unwrap_or_default());
+
+                Some((task, delay))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use crate::{Task, TaskId};
+
+/// Tracks the `uniq_hash` of every pending/running task, so
+/// `TaskQueue::enqueue_unique` can refuse a duplicate instead of enqueuing
+/// it. Entries are released once their task reaches a terminal status.
+#[derive(Debug, Clone, Default)]
+pub struct UniqueIndex {
+    live: Arc<RwLock<HashMap<String, TaskId>>>,
+}
+
+impl UniqueIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserves `hash` for `id` if it isn't already claimed by another live
+    /// task, returning the id already holding it on conflict.
+    pub async fn try_reserve(&self, hash: &str, id: &TaskId) -> Result<(), TaskId> {
+        let mut live = self.live.write().await;
+
+        if let Some(existing) = live.get(hash) {
+            return Err(existing.clone());
+        }
+
+        live.insert(hash.to_string(), id.clone());
+        Ok(())
+    }
+
+    /// Frees `hash` once `id`'s task reaches a terminal status, provided
+    /// `id` is still the one holding the reservation. A task that was never
+    /// reserved (enqueued via `enqueue` rather than `enqueue_unique`, e.g. a
+    /// cron-retemplated task carrying over the template's `uniq_hash`) must
+    /// not evict an unrelated task's still-live reservation on the same hash.
+    pub async fn release(&self, hash: &str, id: &TaskId) {
+        let mut live = self.live.write().await;
+
+        if live.get(hash) == Some(id) {
+            live.remove(hash);
+        }
+    }
+}
+
+/// The default uniqueness key for a task: a SHA-256 over its `task_type`
+/// and serialized `payload`. Used when a task doesn't supply its own
+/// `uniq_hash`.
+pub(crate) fn derive_uniq_hash(task: &Task) -> Option<String> {
+    let payload = serde_json::to_vec(&task.payload).ok()?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(task.task_type.to_string().as_bytes());
+    hasher.update(&payload);
+
+    Some(format!("{:x}", hasher.finalize()))
+}
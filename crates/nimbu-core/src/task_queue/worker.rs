@@ -0,0 +1,99 @@
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+
+use futures_util::FutureExt;
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+
+use crate::{
+    Task, TaskType,
+    task_queue::{
+        errors::TaskError,
+        messages::{ExecutionEvent, SchedulerCommand},
+        queue::TaskQueue,
+        runnable::Context,
+    },
+};
+
+/// Pulls tasks off a [`TaskQueue`] and executes their [`RunnableTask`](crate::RunnableTask)
+/// payload, reporting the outcome back to the scheduler as an [`ExecutionEvent`].
+///
+/// A worker only ever claims tasks matching its `task_type` (the `"common"`
+/// type when none is set), so separate pools can be run against one queue.
+/// `S` is the shared application state handed to every task's `run` via
+/// [`Context::state`]; use `Worker<()>` when tasks need none.
+#[derive(Debug)]
+pub struct Worker<S = ()> {
+    queue: Arc<TaskQueue>,
+    scheduler_tx: mpsc::Sender<SchedulerCommand>,
+    task_type: Option<TaskType>,
+    state: Arc<S>,
+}
+
+impl<S: Send + Sync + 'static> Worker<S> {
+    pub fn new(queue: Arc<TaskQueue>, scheduler_tx: mpsc::Sender<SchedulerCommand>, state: Arc<S>) -> Self {
+        Self {
+            queue,
+            scheduler_tx,
+            task_type: None,
+            state,
+        }
+    }
+
+    /// Scopes this worker to only claim tasks of the given type.
+    pub fn for_task_type(mut self, task_type: TaskType) -> Self {
+        self.task_type = Some(task_type);
+        self
+    }
+
+    /// Runs the worker loop until `dequeue` returns `None` or the scheduler
+    /// channel closes. With [`InMemoryBackend`](crate::InMemoryBackend) the
+    /// ready channels stay open for as long as the queue exists, so the loop
+    /// does not end on its own; with [`SqlBackend`](crate::SqlBackend) it ends
+    /// as soon as no task is ready to be claimed.
+    pub async fn run(&self) {
+        while let Some(mut task) = self.queue.dequeue(self.task_type.as_ref()).await {
+            if let Err(err) = task.assign() {
+                warn!(task_id = ?task.id, %err, "worker could not assign task");
+                continue;
+            }
+            if let Err(err) = task.start() {
+                warn!(task_id = ?task.id, %err, "worker could not start task");
+                continue;
+            }
+
+            let event = self.execute(task).await;
+            if self
+                .scheduler_tx
+                .send(SchedulerCommand::ExecutionResult(event))
+                .await
+                .is_err()
+            {
+                error!("scheduler channel closed while reporting execution result");
+                break;
+            }
+        }
+    }
+
+    async fn execute(&self, task: Task) -> ExecutionEvent {
+        let state: Arc<dyn Any + Send + Sync> = self.state.clone();
+        let ctx = Context::new(task.id.clone(), self.queue.clone(), state);
+
+        match AssertUnwindSafe(task.payload.run(&ctx)).catch_unwind().await {
+            Ok(Ok(())) => ExecutionEvent::Completed(task),
+            Ok(Err(TaskError::Retryable(err))) => ExecutionEvent::RetryableFailure(task, err),
+            Ok(Err(TaskError::Fatal(err))) => ExecutionEvent::FatalFailure(task, err),
+            Err(panic) => {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "task panicked".to_string());
+
+                error!(task_id = ?task.id, %message, "task panicked during execution");
+                ExecutionEvent::RetryableFailure(task, message)
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use nimbu_core::{BackoffStrategy, Context, RetryPolicy, RunnableTask, Task, TaskError};
+use nimbu_core::task_queue::backoff::compute_backoff;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NoopTask;
+
+#[async_trait]
+#[typetag::serde]
+impl RunnableTask for NoopTask {
+    async fn run(&self, _ctx: &Context) -> Result<(), TaskError> {
+        Ok(())
+    }
+}
+
+fn task_with(strategy: BackoffStrategy) -> Task {
+    Task::new(Box::new(NoopTask))
+        .retry_policy(RetryPolicy {
+            max_retries: 5,
+            strategy,
+        })
+        .build()
+}
+
+#[test]
+fn compute_backoff_is_none_without_a_retry_policy() {
+    let mut task = Task::new(Box::new(NoopTask)).build();
+    assert_eq!(compute_backoff(&mut task), None);
+}
+
+#[test]
+fn fixed_backoff_never_changes() {
+    let mut task = task_with(BackoffStrategy::Fixed(Duration::from_millis(250)));
+
+    for _ in 0..3 {
+        assert_eq!(compute_backoff(&mut task), Some(Duration::from_millis(250)));
+    }
+}
+
+#[test]
+fn exponential_backoff_grows_and_caps() {
+    let mut task = task_with(BackoffStrategy::Exponential {
+        base: Duration::from_millis(100),
+        factor: 2,
+        max_delay: Duration::from_millis(300),
+    });
+
+    task.attempts = 0;
+    assert_eq!(compute_backoff(&mut task), Some(Duration::from_millis(100)));
+    task.attempts = 1;
+    assert_eq!(compute_backoff(&mut task), Some(Duration::from_millis(200)));
+    task.attempts = 2;
+    // 100 * 2^2 = 400ms, capped to the 300ms max_delay
+    assert_eq!(compute_backoff(&mut task), Some(Duration::from_millis(300)));
+}
+
+#[test]
+fn jitter_backoff_stays_within_decorrelated_bounds() {
+    let mut task = task_with(BackoffStrategy::ExponentialJitter {
+        base: Duration::from_millis(50),
+        factor: 3,
+        max_delay: Duration::from_secs(5),
+    });
+
+    for _ in 0..20 {
+        let delay = compute_backoff(&mut task).unwrap();
+        assert!(delay >= Duration::from_millis(50));
+        assert!(delay <= Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use nimbu_core::{Context, RunnableTask, Task, TaskError, TaskQueue};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NoopTask;
+
+#[async_trait]
+#[typetag::serde]
+impl RunnableTask for NoopTask {
+    async fn run(&self, _ctx: &Context) -> Result<(), TaskError> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn enqueue_cron_accepts_a_valid_expression() {
+    let queue = TaskQueue::new(5);
+    let template = Task::new(Box::new(NoopTask)).build();
+
+    assert!(queue.enqueue_cron(template, "0 0 0 * * *").is_ok());
+
+    queue.shutdown().await;
+}
+
+#[tokio::test]
+async fn enqueue_cron_rejects_an_invalid_expression() {
+    let queue = TaskQueue::new(5);
+    let template = Task::new(Box::new(NoopTask)).build();
+
+    assert!(queue.enqueue_cron(template, "not a cron expression").is_err());
+
+    queue.shutdown().await;
+}
@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use nimbu_core::{Context, RetainedTasks, RetentionMode, RunnableTask, Task, TaskError, TaskStatus};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NoopTask;
+
+#[async_trait]
+#[typetag::serde]
+impl RunnableTask for NoopTask {
+    async fn run(&self, _ctx: &Context) -> Result<(), TaskError> {
+        Ok(())
+    }
+}
+
+fn task_in(status: TaskStatus) -> Task {
+    let mut task = Task::new(Box::new(NoopTask)).build();
+    task.status = status;
+    task
+}
+
+#[tokio::test]
+async fn remove_all_drops_every_outcome() {
+    let retained = RetainedTasks::new(RetentionMode::RemoveAll);
+    let task = task_in(TaskStatus::Completed);
+
+    retained.record(&task).await;
+    assert_eq!(retained.status(&task.id).await, None);
+}
+
+#[tokio::test]
+async fn keep_failed_ignores_completions_but_keeps_permanent_failures() {
+    let retained = RetainedTasks::new(RetentionMode::KeepFailed);
+
+    let completed = task_in(TaskStatus::Completed);
+    retained.record(&completed).await;
+    assert_eq!(retained.status(&completed.id).await, None);
+
+    let failed = task_in(TaskStatus::FailedPermanent {
+        error: "boom".into(),
+    });
+    retained.record(&failed).await;
+    assert_eq!(
+        retained.status(&failed.id).await,
+        Some(TaskStatus::FailedPermanent {
+            error: "boom".into()
+        })
+    );
+}
+
+#[tokio::test]
+async fn keep_all_retains_every_outcome() {
+    let retained = RetainedTasks::new(RetentionMode::KeepAll);
+    let task = task_in(TaskStatus::Completed);
+
+    retained.record(&task).await;
+    assert_eq!(retained.status(&task.id).await, Some(TaskStatus::Completed));
+}
@@ -1,58 +1,118 @@
 use std::time::Duration;
 
-use nimbu_core::{Task, task_queues::TaskQueue};
-use tokio::time::{advance, pause, timeout};
+use async_trait::async_trait;
+use nimbu_core::{Context, EnqueueOutcome, RunnableTask, Task, TaskError, TaskQueue, TaskType};
+use serde::{Deserialize, Serialize};
+use tokio::time::timeout;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct NoopTask;
+
+#[async_trait]
+#[typetag::serde]
+impl RunnableTask for NoopTask {
+    async fn run(&self, _ctx: &Context) -> Result<(), TaskError> {
+        Ok(())
+    }
+}
+
+fn noop_task() -> Task {
+    Task::new(Box::new(NoopTask)).build()
+}
 
 #[tokio::test]
 async fn fifo_enqueue_dequeue() {
-    let mut queue = TaskQueue::new(10);
+    let queue = TaskQueue::new(10);
 
-    let t1 = Task::default();
-    let t2 = Task::default();
+    let t1 = noop_task();
+    let t2 = noop_task();
+    let (id1, id2) = (t1.id.clone(), t2.id.clone());
 
-    queue.enqueue(t1.clone()).await.unwrap();
-    queue.enqueue(t2.clone()).await.unwrap();
+    queue.enqueue(t1).await.unwrap();
+    queue.enqueue(t2).await.unwrap();
 
-    let r1 = queue.dequeue().await.unwrap();
-    let r2 = queue.dequeue().await.unwrap();
+    let r1 = queue.dequeue(None).await.unwrap();
+    let r2 = queue.dequeue(None).await.unwrap();
 
-    assert_eq!(r1.id, t1.id);
-    assert_eq!(r2.id, t2.id);
+    assert_eq!(r1.id, id1);
+    assert_eq!(r2.id, id2);
+
+    queue.shutdown().await;
 }
 
 #[tokio::test]
-async fn capacity_backpressure_blocks() {
-    let mut queue = TaskQueue::new(1);
-    queue.enqueue(Task::default()).await.unwrap();
+async fn dequeue_only_returns_matching_task_type() {
+    let queue = TaskQueue::new(10);
 
-    let fut = queue.enqueue(Task::default());
-    assert!(timeout(Duration::from_millis(50), fut).await.is_err());
+    let common = noop_task();
+    let mut scoped = noop_task();
+    scoped.task_type = TaskType::new("reports");
+    let scoped_id = scoped.id.clone();
 
-    queue.dequeue().await.unwrap();
+    queue.enqueue(common).await.unwrap();
+    queue.enqueue(scoped).await.unwrap();
+
+    let report_task = queue
+        .dequeue(Some(&TaskType::new("reports")))
+        .await
+        .unwrap();
+    assert_eq!(report_task.id, scoped_id);
+
+    // the common task is still waiting for an unscoped worker
+    assert!(queue.dequeue(None).await.is_some());
+
+    queue.shutdown().await;
 }
 
 #[tokio::test]
-async fn delayed_enqueue_is_deterministic() {
-    pause();
+async fn delayed_enqueue_fires_after_the_delay() {
+    let queue = TaskQueue::new(5);
+    let task = noop_task();
+    let id = task.id.clone();
+
+    queue.enqueue_delayed(task, Duration::from_millis(60));
+
+    assert!(
+        timeout(Duration::from_millis(20), queue.dequeue(None))
+            .await
+            .is_err(),
+        "task should not be ready before its delay elapses"
+    );
+
+    let out = timeout(Duration::from_millis(500), queue.dequeue(None))
+        .await
+        .expect("task should become ready once its delay elapses")
+        .unwrap();
+    assert_eq!(out.id, id);
 
-    let mut queue = TaskQueue::new(5);
-    let task = Task::default();
-    let id = task.clone().id;
+    queue.shutdown().await;
+}
 
-    queue.enqueue_delayed(task, Duration::from_secs(10));
+#[tokio::test]
+async fn enqueue_unique_refuses_a_live_duplicate() {
+    let queue = TaskQueue::new(5);
 
-    // assert_eq!(queue.len(), 0);
+    let mut original = noop_task();
+    original.uniq_hash = Some("same-key".into());
+    let original_id = original.id.clone();
 
-    advance(Duration::from_secs(9)).await;
-    assert!(queue.dequeue().await.is_none());
+    let mut duplicate = noop_task();
+    duplicate.uniq_hash = Some("same-key".into());
 
-    advance(Duration::from_secs(1)).await;
-    let out = queue.dequeue().await.unwrap();
-    assert_eq!(out.id, id);
+    assert_eq!(
+        queue.enqueue_unique(original).await.unwrap(),
+        EnqueueOutcome::Enqueued
+    );
+    assert_eq!(
+        queue.enqueue_unique(duplicate).await.unwrap(),
+        EnqueueOutcome::Duplicate(original_id)
+    );
+
+    queue.shutdown().await;
 }
 
 #[tokio::test]
-async fn shutdown_stops_queue() {
+async fn shutdown_stops_the_scheduler() {
     let queue = TaskQueue::new(5);
     queue.shutdown().await;
 }
@@ -0,0 +1,30 @@
+use nimbu_core::{TaskId, UniqueIndex};
+
+#[tokio::test]
+async fn try_reserve_rejects_a_live_duplicate() {
+    let index = UniqueIndex::new();
+    let a = TaskId::new();
+    let b = TaskId::new();
+
+    assert_eq!(index.try_reserve("hash", &a).await, Ok(()));
+    assert_eq!(index.try_reserve("hash", &b).await, Err(a));
+}
+
+#[tokio::test]
+async fn release_only_frees_the_reservation_it_still_holds() {
+    let index = UniqueIndex::new();
+    let a = TaskId::new();
+    let b = TaskId::new();
+
+    index.try_reserve("hash", &a).await.unwrap();
+
+    // `b` never held "hash" (e.g. it was enqueued directly, bypassing
+    // `enqueue_unique`), so its terminal state must not evict `a`'s
+    // still-live reservation.
+    index.release("hash", &b).await;
+    assert_eq!(index.try_reserve("hash", &b).await, Err(a.clone()));
+
+    // once `a` itself finishes, its own release does free the hash
+    index.release("hash", &a).await;
+    assert_eq!(index.try_reserve("hash", &b).await, Ok(()));
+}
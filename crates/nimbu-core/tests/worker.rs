@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use nimbu_core::{Context, RunnableTask, Task, TaskError, TaskQueue, Worker};
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, timeout};
+
+#[derive(Debug, Default)]
+struct AppState {
+    calls: AtomicUsize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CountingTask;
+
+#[async_trait]
+#[typetag::serde]
+impl RunnableTask for CountingTask {
+    async fn run(&self, ctx: &Context) -> Result<(), TaskError> {
+        let state = ctx
+            .state::<AppState>()
+            .expect("context state should downcast to the worker's AppState");
+        state.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn worker_passes_shared_state_into_tasks() {
+    let queue = Arc::new(TaskQueue::new(5));
+    let state = Arc::new(AppState::default());
+
+    queue
+        .enqueue(Task::new(Box::new(CountingTask)).build())
+        .await
+        .unwrap();
+
+    let worker = Worker::new(queue.clone(), queue.scheduler_tx.clone(), state.clone());
+    let handle = tokio::spawn(async move { worker.run().await });
+
+    timeout(Duration::from_millis(500), async {
+        while state.calls.load(Ordering::SeqCst) == 0 {
+            sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("counting task should have run and incremented the shared state");
+
+    handle.abort();
+    let _ = handle.await;
+
+    let queue = Arc::try_unwrap(queue).expect("no other Arc handles should remain");
+    queue.shutdown().await;
+}